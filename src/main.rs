@@ -1,11 +1,30 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use image::{DynamicImage, ImageFormat, RgbaImage};
+use notify::event::{ModifyKind, RenameMode};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::channel;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use walkdir::WalkDir;
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+/// Manifest file name, kept at the root of the output directory
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Number of hex characters of the content hash kept in output filenames
+const HASH_LEN: usize = 12;
+
+/// Monotonic counter used to make concurrent temp-file names collision-free
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 /// SVG to PNG converter with file watching
 #[derive(Parser, Debug)]
@@ -27,66 +46,637 @@ struct Args {
     #[arg(short = 'n', long, default_value_t = false)]
     no_watch: bool,
 
+    /// Name outputs by content hash (stem.<hash>.<ext>, ext following --format) and maintain manifest.json
+    #[arg(long, default_value_t = false)]
+    hash: bool,
+
+    /// Output image format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Png)]
+    format: OutputFormat,
+
+    /// Uniform scale factor applied to the SVG's intrinsic size (ignored if --width/--height given)
+    #[arg(long)]
+    scale: Option<f32>,
+
+    /// Explicit output width in pixels (aspect preserved if --height is omitted)
+    #[arg(long)]
+    width: Option<u32>,
+
+    /// Explicit output height in pixels (aspect preserved if --width is omitted)
+    #[arg(long)]
+    height: Option<u32>,
+
+    /// Background color (RRGGBB hex) used to flatten transparency for formats without alpha
+    #[arg(long, default_value = "FFFFFF")]
+    background: String,
+
+    /// Wait this many milliseconds of quiet on a path before converting it, coalescing bursts
+    /// of events from a single save
+    #[arg(long, default_value_t = 200)]
+    debounce_ms: u64,
+
     /// Print version
     #[arg(short = 'v', long = "version")]
     version: bool,
 }
 
-/// Convert an SVG file to PNG
-fn convert(svg: &Path, png: &Path) -> Result<()> {
-    // Read SVG file data
-    let svg_data = fs::read(svg)?;
+/// Raster output format to encode the rendered pixmap as
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl OutputFormat {
+    /// File extension (without the leading dot) used for this format
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png => "png",
+            OutputFormat::Jpeg => "jpg",
+            OutputFormat::Webp => "webp",
+        }
+    }
+}
+
+/// Resolved rendering configuration shared across all conversions in a run
+#[derive(Clone, Debug)]
+struct RenderOptions {
+    format: OutputFormat,
+    scale: Option<f32>,
+    width: Option<u32>,
+    height: Option<u32>,
+    /// Background color used to flatten alpha for formats that don't support transparency
+    background: [u8; 3],
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::Png,
+            scale: None,
+            width: None,
+            height: None,
+            background: [0xff, 0xff, 0xff],
+        }
+    }
+}
+
+impl RenderOptions {
+    fn from_args(args: &Args) -> Result<Self> {
+        Ok(Self {
+            format: args.format,
+            scale: args.scale,
+            width: args.width,
+            height: args.height,
+            background: parse_hex_color(&args.background)?,
+        })
+    }
+}
+
+/// Parse a `RRGGBB` hex string into an RGB triple
+fn parse_hex_color(hex: &str) -> Result<[u8; 3]> {
+    let hex = hex.trim_start_matches('#');
+    if hex.len() != 6 {
+        anyhow::bail!("Invalid background color {hex:?}: expected 6 hex digits (RRGGBB)");
+    }
+    let channel = |range| u8::from_str_radix(&hex[range], 16);
+    Ok([channel(0..2)?, channel(2..4)?, channel(4..6)?])
+}
+
+/// Resolve the pixel dimensions to render at, given the SVG's intrinsic size and the requested
+/// scale/width/height. Explicit width/height win; a lone one preserves aspect ratio.
+fn target_dimensions(
+    intrinsic_width: f32,
+    intrinsic_height: f32,
+    scale: Option<f32>,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> (u32, u32) {
+    let aspect = intrinsic_height / intrinsic_width;
+
+    match (width, height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, ((w as f32) * aspect).round().max(1.0) as u32),
+        (None, Some(h)) => (((h as f32) / aspect).round().max(1.0) as u32, h),
+        (None, None) => {
+            let scale = scale.unwrap_or(1.0);
+            (
+                (intrinsic_width * scale).round().max(1.0) as u32,
+                (intrinsic_height * scale).round().max(1.0) as u32,
+            )
+        }
+    }
+}
 
-    // Create font database and load system fonts for text rendering
+/// Build the `usvg::Options` used to parse every SVG in a run, loading system fonts exactly once
+fn build_usvg_options() -> usvg::Options {
     let mut fontdb = fontdb::Database::new();
     fontdb.load_system_fonts();
 
-    // Parse SVG with font database for proper text rendering
-    let opt = usvg::Options {
+    usvg::Options {
         fontdb: Arc::new(fontdb),
         ..Default::default()
-    };
+    }
+}
 
-    let tree = usvg::Tree::from_data(&svg_data, &opt)?;
+/// Render an SVG file and encode it per `opts` (format, sizing, background)
+fn render_image_bytes(
+    svg: &Path,
+    usvg_opts: &usvg::Options,
+    opts: &RenderOptions,
+) -> Result<Vec<u8>> {
+    // Read SVG file data
+    let svg_data = fs::read(svg)?;
 
-    // Get the SVG size
-    let size = tree.size();
+    // Parse SVG with the shared font database for proper text rendering
+    let tree = usvg::Tree::from_data(&svg_data, usvg_opts)?;
 
-    // Create a pixmap
-    let mut pixmap = tiny_skia::Pixmap::new(size.width() as u32, size.height() as u32)
+    // Get the SVG's intrinsic size and resolve the requested output dimensions
+    let size = tree.size();
+    let (width, height) = target_dimensions(
+        size.width(),
+        size.height(),
+        opts.scale,
+        opts.width,
+        opts.height,
+    );
+
+    // Create a pixmap at the resolved resolution
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
         .ok_or_else(|| anyhow::anyhow!("Failed to create pixmap"))?;
 
-    // Render SVG to pixmap
-    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    // Rasterize at the resolved resolution, rather than rendering at intrinsic size and
+    // stretching the bitmap afterwards
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / size.width(),
+        height as f32 / size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    encode_pixmap(&pixmap, opts)
+}
+
+/// Encode a rendered pixmap into the requested output format
+fn encode_pixmap(pixmap: &tiny_skia::Pixmap, opts: &RenderOptions) -> Result<Vec<u8>> {
+    if opts.format == OutputFormat::Png {
+        return Ok(pixmap.encode_png()?);
+    }
+
+    // `pixmap.data()` is premultiplied alpha, but `image`'s encoders expect straight alpha —
+    // demultiply per pixel first or colors come out darkened wherever alpha is partial.
+    let mut image = RgbaImage::from_raw(
+        pixmap.width(),
+        pixmap.height(),
+        straight_alpha_bytes(pixmap),
+    )
+    .ok_or_else(|| anyhow::anyhow!("Failed to build image buffer from pixmap"))?;
+
+    let dynamic_image = if opts.format == OutputFormat::Jpeg {
+        // JPEG has no alpha channel: flatten onto the configured background color first
+        flatten_onto_background(&mut image, opts.background);
+        DynamicImage::ImageRgba8(image).to_rgb8().into()
+    } else {
+        DynamicImage::ImageRgba8(image)
+    };
+
+    let image_format = match opts.format {
+        OutputFormat::Jpeg => ImageFormat::Jpeg,
+        OutputFormat::Webp => ImageFormat::WebP,
+        OutputFormat::Png => unreachable!("PNG is handled above"),
+    };
+
+    let mut bytes = Vec::new();
+    dynamic_image.write_to(&mut Cursor::new(&mut bytes), image_format)?;
+    Ok(bytes)
+}
+
+/// Convert a pixmap's premultiplied RGBA buffer into straight-alpha RGBA bytes, as expected by
+/// the `image` crate's encoders
+fn straight_alpha_bytes(pixmap: &tiny_skia::Pixmap) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(pixmap.pixels().len() * 4);
+    for pixel in pixmap.pixels() {
+        let color = pixel.demultiply();
+        bytes.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+    }
+    bytes
+}
+
+/// Alpha-composite `image` onto a solid `background` color in place
+fn flatten_onto_background(image: &mut RgbaImage, background: [u8; 3]) {
+    for pixel in image.pixels_mut() {
+        let alpha = pixel[3] as f32 / 255.0;
+        for channel in 0..3 {
+            let src = pixel[channel] as f32;
+            let bg = background[channel] as f32;
+            pixel[channel] = (src * alpha + bg * (1.0 - alpha)).round() as u8;
+        }
+        pixel[3] = 255;
+    }
+}
+
+/// Write `bytes` to `dest` via a temp-file-and-rename so readers never observe a partial file
+fn write_atomic(dest: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp_path = temp_path_for(dest);
+    if let Err(e) = fs::write(&tmp_path, bytes) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    if let Err(e) = fs::rename(&tmp_path, dest) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Convert an SVG file to a raster image per `opts`
+fn convert(svg: &Path, dest: &Path, usvg_opts: &usvg::Options, opts: &RenderOptions) -> Result<()> {
+    let image_bytes = render_image_bytes(svg, usvg_opts, opts)?;
+    write_atomic(dest, &image_bytes)?;
 
-    // Save as PNG
-    pixmap.save_png(png)?;
+    println!("Converted: {} â†’ {}", svg.display(), dest.display());
 
-    println!("Converted: {} â†’ {}", svg.display(), png.display());
+    Ok(())
+}
+
+/// Build a temp-file path next to `dest` so the final rename is a same-filesystem move
+fn temp_path_for(dest: &Path) -> PathBuf {
+    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let file_name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    let tmp_name = format!("{file_name}.tmp-{}-{counter}", std::process::id());
+    dest.with_file_name(tmp_name)
+}
+
+/// Whether `path` has a `.svg` extension
+fn is_svg(path: &Path) -> bool {
+    path.extension().and_then(|s| s.to_str()) == Some("svg")
+}
+
+/// Mirror an SVG's location under `input_root` onto `output_root`, swapping in `extension`
+fn mirrored_output_path(
+    input_root: &Path,
+    output_root: &Path,
+    svg_path: &Path,
+    extension: &str,
+) -> Result<PathBuf> {
+    let relative = svg_path
+        .strip_prefix(input_root)
+        .unwrap_or_else(|_| svg_path.file_name().map_or(svg_path, Path::new));
+
+    let mut output_path = output_root.join(relative);
+    output_path.set_extension(extension);
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    Ok(output_path)
+}
+
+/// Collect the paths of all `.svg` files under `root`, recursing into subdirectories
+fn collect_svg_paths(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.path().to_path_buf())
+        .filter(|p| is_svg(p))
+        .collect()
+}
+
+/// Convert all existing SVG files in a directory, recursing into subdirectories.
+/// Files are converted in parallel across cores; failures are collected and reported
+/// as a summary at the end rather than aborting the whole run.
+fn convert_existing_files(
+    input_path: &Path,
+    output_path: &Path,
+    usvg_opts: &Arc<usvg::Options>,
+    opts: &RenderOptions,
+) -> Result<()> {
+    let svg_paths = collect_svg_paths(input_path);
+
+    let errors: Vec<(PathBuf, anyhow::Error)> = svg_paths
+        .par_iter()
+        .filter_map(|path| {
+            let result =
+                mirrored_output_path(input_path, output_path, path, opts.format.extension())
+                    .and_then(|dest_path| convert(path, &dest_path, usvg_opts, opts));
+
+            result.err().map(|e| (path.clone(), e))
+        })
+        .collect();
+
+    report_conversion_errors(&errors);
+    Ok(())
+}
+
+/// Print a summary of per-file conversion failures, if any
+fn report_conversion_errors(errors: &[(PathBuf, anyhow::Error)]) {
+    if errors.is_empty() {
+        return;
+    }
+
+    eprintln!("Finished with {} error(s):", errors.len());
+    for (path, e) in errors {
+        eprintln!("  {}: {}", path.display(), e);
+    }
+}
+
+/// First `HASH_LEN` hex characters of the SHA-256 digest of `bytes`
+fn hash_hex(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    format!("{digest:x}")[..HASH_LEN].to_string()
+}
+
+/// Manifest key for an SVG: its path relative to the input root, with `/` separators
+fn manifest_key(input_root: &Path, svg_path: &Path) -> String {
+    svg_path
+        .strip_prefix(input_root)
+        .unwrap_or(svg_path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Load `manifest.json` from the output root, or an empty manifest if it doesn't exist yet
+fn load_manifest(output_path: &Path) -> Result<Map<String, Value>> {
+    let manifest_path = output_path.join(MANIFEST_FILE);
+    match fs::read(&manifest_path) {
+        Ok(data) => Ok(serde_json::from_slice(&data)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Map::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write `manifest.json` back to the output root, pretty-printed for readability
+fn save_manifest(output_path: &Path, manifest: &Map<String, Value>) -> Result<()> {
+    let manifest_path = output_path.join(MANIFEST_FILE);
+    let json = serde_json::to_vec_pretty(manifest)?;
+    write_atomic(&manifest_path, &json)
+}
+
+/// Render and (idempotently) write a content-hashed image for `svg`, updating `manifest` in place.
+/// Skips rewriting when the rendered bytes hash to the value already recorded for this SVG.
+fn convert_hashed(
+    svg: &Path,
+    input_path: &Path,
+    output_path: &Path,
+    manifest: &mut Map<String, Value>,
+    usvg_opts: &usvg::Options,
+    opts: &RenderOptions,
+) -> Result<()> {
+    let key = manifest_key(input_path, svg);
+    let relative_dir = Path::new(&key).parent().map(Path::to_path_buf);
+    let stem = svg
+        .file_stem()
+        .ok_or_else(|| anyhow::anyhow!("SVG path has no file stem: {}", svg.display()))?
+        .to_string_lossy()
+        .into_owned();
+
+    let image_bytes = render_image_bytes(svg, usvg_opts, opts)?;
+    let hash = hash_hex(&image_bytes);
+    let extension = opts.format.extension();
+    let hashed_name = format!("{stem}.{hash}.{extension}");
+    let relative_dest = relative_dir
+        .map(|dir| dir.join(&hashed_name))
+        .unwrap_or_else(|| PathBuf::from(&hashed_name));
+    let relative_dest_str = relative_dest.to_string_lossy().replace('\\', "/");
+
+    let previous = manifest.get(&key).and_then(Value::as_str).map(String::from);
+    let dest_path = output_path.join(&relative_dest);
+
+    if previous.as_deref() == Some(relative_dest_str.as_str()) && dest_path.exists() {
+        // Content unchanged since the last run: nothing to write.
+        return Ok(());
+    }
+
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic(&dest_path, &image_bytes)?;
+
+    // Remove the previous hashed file now that it's superseded, so the output dir doesn't
+    // accumulate an image per historical revision of the SVG.
+    if let Some(previous) = previous.filter(|p| p != &relative_dest_str) {
+        let _ = fs::remove_file(output_path.join(previous));
+    }
+
+    manifest.insert(key, Value::String(relative_dest_str));
+    println!("Converted: {} â†’ {}", svg.display(), dest_path.display());
 
     Ok(())
 }
 
-/// Convert all existing SVG files in a directory
-fn convert_existing_files(input_path: &Path, output_path: &Path) -> Result<()> {
-    for entry in fs::read_dir(input_path)? {
-        let entry = entry?;
-        let path = entry.path();
+/// Convert all existing SVG files using content-hashed output names, maintaining `manifest.json`
+fn convert_existing_files_hashed(
+    input_path: &Path,
+    output_path: &Path,
+    usvg_opts: &usvg::Options,
+    opts: &RenderOptions,
+) -> Result<()> {
+    let mut manifest = load_manifest(output_path)?;
+    let mut seen_keys = std::collections::HashSet::new();
+
+    for path in collect_svg_paths(input_path) {
+        seen_keys.insert(manifest_key(input_path, &path));
+
+        if let Err(e) = convert_hashed(
+            &path,
+            input_path,
+            output_path,
+            &mut manifest,
+            usvg_opts,
+            opts,
+        ) {
+            eprintln!("Error converting {}: {}", path.display(), e);
+        }
+    }
+
+    prune_stale_manifest_entries(output_path, &mut manifest, &seen_keys);
+    save_manifest(output_path, &manifest)
+}
 
-        if path.is_file()
-            && path.extension().and_then(|s| s.to_str()) == Some("svg")
-            && let Some(stem) = path.file_stem()
+/// Remove manifest entries (and their output images) whose source SVG no longer exists
+fn prune_stale_manifest_entries(
+    output_path: &Path,
+    manifest: &mut Map<String, Value>,
+    seen_keys: &std::collections::HashSet<String>,
+) {
+    let stale_keys: Vec<String> = manifest
+        .keys()
+        .filter(|key| !seen_keys.contains(*key))
+        .cloned()
+        .collect();
+
+    for key in stale_keys {
+        if let Some(relative_png) = manifest
+            .remove(&key)
+            .and_then(|v| v.as_str().map(String::from))
         {
-            let mut png_path = output_path.to_path_buf();
-            png_path.push(stem);
-            png_path.set_extension("png");
+            let _ = fs::remove_file(output_path.join(relative_png));
+        }
+    }
+}
+
+/// Delete the output corresponding to a deleted or renamed-away SVG, keeping the output
+/// directory (and the manifest, in hash mode) in sync with the source tree
+fn handle_removed_svg(
+    svg_path: &Path,
+    input_path: &Path,
+    output_path: &Path,
+    hashed_manifest: &mut Option<Map<String, Value>>,
+    render_opts: &RenderOptions,
+) {
+    if let Some(manifest) = hashed_manifest.as_mut() {
+        let key = manifest_key(input_path, svg_path);
+        if let Some(relative) = manifest
+            .remove(&key)
+            .and_then(|v| v.as_str().map(String::from))
+        {
+            let _ = fs::remove_file(output_path.join(relative));
+        }
+        if let Err(e) = save_manifest(output_path, manifest) {
+            eprintln!(
+                "Error updating manifest after removing {}: {}",
+                svg_path.display(),
+                e
+            );
+        }
+    } else if let Ok(dest_path) = mirrored_output_path(
+        input_path,
+        output_path,
+        svg_path,
+        render_opts.format.extension(),
+    ) {
+        let _ = fs::remove_file(dest_path);
+    }
+}
 
-            if let Err(e) = convert(&path, &png_path) {
-                eprintln!("Error converting {}: {}", path.display(), e);
+/// Convert one SVG that has been quiet for the debounce window
+fn convert_debounced(
+    svg_path: &Path,
+    input_path: &Path,
+    output_path: &Path,
+    hashed_manifest: &mut Option<Map<String, Value>>,
+    usvg_opts: &usvg::Options,
+    render_opts: &RenderOptions,
+) {
+    if let Some(manifest) = hashed_manifest.as_mut() {
+        if let Err(e) = convert_hashed(
+            svg_path,
+            input_path,
+            output_path,
+            manifest,
+            usvg_opts,
+            render_opts,
+        )
+        .and_then(|()| save_manifest(output_path, manifest))
+        {
+            eprintln!("Error converting {}: {}", svg_path.display(), e);
+        }
+        return;
+    }
+
+    match mirrored_output_path(
+        input_path,
+        output_path,
+        svg_path,
+        render_opts.format.extension(),
+    ) {
+        Ok(dest_path) => {
+            if let Err(e) = convert(svg_path, &dest_path, usvg_opts, render_opts) {
+                eprintln!("Error converting {}: {}", svg_path.display(), e);
             }
         }
+        Err(e) => eprintln!(
+            "Error resolving output path for {}: {}",
+            svg_path.display(),
+            e
+        ),
+    }
+}
+
+/// Fold one filesystem event into the debounce queue, or act on it immediately if it's a
+/// deletion/rename that needs the output tree kept in sync right away
+fn handle_watch_event(
+    event: Event,
+    pending: &mut HashMap<PathBuf, Instant>,
+    input_path: &Path,
+    output_path: &Path,
+    hashed_manifest: &mut Option<Map<String, Value>>,
+    render_opts: &RenderOptions,
+) {
+    match event.kind {
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Any) => {
+            for path in event.paths {
+                if is_svg(&path) {
+                    pending.insert(path, Instant::now());
+                }
+            }
+        }
+        // A rename that reports both the old and new path: sync the old output away, then
+        // queue the new path like any other create.
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+            let from = &event.paths[0];
+            let to = &event.paths[1];
+            if is_svg(from) {
+                pending.remove(from);
+                handle_removed_svg(from, input_path, output_path, hashed_manifest, render_opts);
+            }
+            if is_svg(to) {
+                pending.insert(to.clone(), Instant::now());
+            }
+        }
+        // Platforms that report a rename as separate From/To events instead
+        EventKind::Modify(ModifyKind::Name(_)) | EventKind::Remove(_) => {
+            for path in &event.paths {
+                if !is_svg(path) {
+                    continue;
+                }
+                pending.remove(path);
+                if path.exists() {
+                    pending.insert(path.clone(), Instant::now());
+                } else {
+                    handle_removed_svg(path, input_path, output_path, hashed_manifest, render_opts);
+                }
+            }
+        }
+        _ => {
+            // Ignore other event kinds (access, metadata-only changes, etc.)
+        }
+    }
+}
+
+/// Convert every path that has been quiet for at least `debounce`, removing it from `pending`
+fn flush_due_conversions(
+    pending: &mut HashMap<PathBuf, Instant>,
+    debounce: Duration,
+    input_path: &Path,
+    output_path: &Path,
+    hashed_manifest: &mut Option<Map<String, Value>>,
+    usvg_opts: &usvg::Options,
+    render_opts: &RenderOptions,
+) {
+    let now = Instant::now();
+    let due: Vec<PathBuf> = pending
+        .iter()
+        .filter(|(_, &last_seen)| now.duration_since(last_seen) >= debounce)
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    for path in due {
+        pending.remove(&path);
+        convert_debounced(
+            &path,
+            input_path,
+            output_path,
+            hashed_manifest,
+            usvg_opts,
+            render_opts,
+        );
     }
-    Ok(())
 }
 
 fn main() -> Result<()> {
@@ -101,6 +691,11 @@ fn main() -> Result<()> {
     // Create input and output directories
     let input_path = PathBuf::from(&args.input);
     let output_path = PathBuf::from(&args.output);
+    let render_opts = RenderOptions::from_args(&args)?;
+
+    // Built once at startup: system font scanning is the dominant cost when converting many
+    // files, so every conversion in this run borrows the same database instead of rebuilding it.
+    let usvg_opts = Arc::new(build_usvg_options());
 
     fs::create_dir_all(&input_path)?;
     fs::create_dir_all(&output_path)?;
@@ -108,7 +703,11 @@ fn main() -> Result<()> {
     // Convert existing files if requested or in no-watch mode
     if args.convert_existing || args.no_watch {
         println!("Converting existing SVG files...");
-        convert_existing_files(&input_path, &output_path)?;
+        if args.hash {
+            convert_existing_files_hashed(&input_path, &output_path, &usvg_opts, &render_opts)?;
+        } else {
+            convert_existing_files(&input_path, &output_path, &usvg_opts, &render_opts)?;
+        }
     }
 
     // If no-watch mode, exit after converting
@@ -125,35 +724,30 @@ fn main() -> Result<()> {
 
     println!("Watching {:?}", args.input);
 
-    // Main event loop
+    // In hash mode the manifest is loaded once and persisted after every update it receives
+    let mut hashed_manifest = if args.hash {
+        Some(load_manifest(&output_path)?)
+    } else {
+        None
+    };
+
+    let debounce = Duration::from_millis(args.debounce_ms);
+    // Paths seen via Create/Modify that are waiting out the debounce window before conversion
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    // Main event loop: poll at a fixed short interval so pending debounced paths get flushed
+    // even when no new events arrive, and fold every event into the pending queue (or act on
+    // it immediately, for removals/renames) as it comes in.
     loop {
-        match rx.recv_timeout(Duration::from_secs(1)) {
-            Ok(Ok(Event {
-                kind: EventKind::Create(_) | EventKind::Modify(_),
-                paths,
-                ..
-            })) => {
-                for path in paths {
-                    // Only process .svg files
-                    if path.extension().and_then(|s| s.to_str()) == Some("svg") {
-                        // Get the file stem (filename without extension)
-                        if let Some(stem) = path.file_stem() {
-                            // Create output path with .png extension
-                            let mut png_path = output_path.clone();
-                            png_path.push(stem);
-                            png_path.set_extension("png");
-
-                            // Convert the file
-                            if let Err(e) = convert(&path, &png_path) {
-                                eprintln!("Error converting {}: {}", path.display(), e);
-                            }
-                        }
-                    }
-                }
-            }
-            Ok(Ok(_)) => {
-                // Ignore other event types
-            }
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(Ok(event)) => handle_watch_event(
+                event,
+                &mut pending,
+                &input_path,
+                &output_path,
+                &mut hashed_manifest,
+                &render_opts,
+            ),
             Ok(Err(e)) => eprintln!("Watch error: {:?}", e),
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                 // Just continue watching
@@ -163,6 +757,16 @@ fn main() -> Result<()> {
                 break;
             }
         }
+
+        flush_due_conversions(
+            &mut pending,
+            debounce,
+            &input_path,
+            &output_path,
+            &mut hashed_manifest,
+            &usvg_opts,
+            &render_opts,
+        );
     }
 
     Ok(())
@@ -174,6 +778,11 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    /// Helper function to build the shared SVG parsing options used by most tests
+    fn test_usvg_options() -> usvg::Options {
+        build_usvg_options()
+    }
+
     /// Helper function to create a simple valid SVG
     fn create_test_svg() -> String {
         r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -202,7 +811,12 @@ mod tests {
         fs::write(&svg_path, create_test_svg()).unwrap();
 
         // Convert it
-        let result = convert(&svg_path, &png_path);
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Conversion should succeed");
         assert!(png_path.exists(), "PNG file should be created");
@@ -222,7 +836,12 @@ mod tests {
         fs::write(&svg_path, create_svg_with_text()).unwrap();
 
         // Convert it
-        let result = convert(&svg_path, &png_path);
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Conversion with text should succeed");
         assert!(png_path.exists(), "PNG file should be created");
@@ -238,7 +857,12 @@ mod tests {
         fs::write(&svg_path, "not a valid svg").unwrap();
 
         // Try to convert it
-        let result = convert(&svg_path, &png_path);
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_err(), "Conversion should fail for invalid SVG");
         // PNG should not be created on error
@@ -255,7 +879,12 @@ mod tests {
         let png_path = temp_dir.path().join("output.png");
 
         // Try to convert a file that doesn't exist
-        let result = convert(&svg_path, &png_path);
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
 
         assert!(
             result.is_err(),
@@ -278,7 +907,12 @@ mod tests {
         fs::write(input_dir.join("test3.svg"), create_svg_with_text()).unwrap();
 
         // Convert all existing files
-        let result = convert_existing_files(&input_dir, &output_dir);
+        let result = convert_existing_files(
+            &input_dir,
+            &output_dir,
+            &Arc::new(test_usvg_options()),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Batch conversion should succeed");
 
@@ -297,6 +931,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_convert_existing_files_nested_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_dir = temp_dir.path().join("input");
+        let output_dir = temp_dir.path().join("output");
+
+        fs::create_dir_all(input_dir.join("a")).unwrap();
+        fs::create_dir_all(input_dir.join("b")).unwrap();
+
+        // Same file stem in two different subdirectories must not collide
+        fs::write(input_dir.join("a").join("icon.svg"), create_test_svg()).unwrap();
+        fs::write(input_dir.join("b").join("icon.svg"), create_test_svg()).unwrap();
+
+        let result = convert_existing_files(
+            &input_dir,
+            &output_dir,
+            &Arc::new(test_usvg_options()),
+            &RenderOptions::default(),
+        );
+
+        assert!(result.is_ok(), "Recursive conversion should succeed");
+        assert!(
+            output_dir.join("a").join("icon.png").exists(),
+            "a/icon.png should exist"
+        );
+        assert!(
+            output_dir.join("b").join("icon.png").exists(),
+            "b/icon.png should exist"
+        );
+    }
+
+    #[test]
+    fn test_convert_existing_files_hashed_writes_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_dir = temp_dir.path().join("input");
+        let output_dir = temp_dir.path().join("output");
+
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("icon.svg"), create_test_svg()).unwrap();
+
+        let result = convert_existing_files_hashed(
+            &input_dir,
+            &output_dir,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
+        assert!(result.is_ok(), "Hashed conversion should succeed");
+
+        let manifest_data = fs::read_to_string(output_dir.join("manifest.json")).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest_data).unwrap();
+        let hashed_name = manifest["icon.svg"].as_str().unwrap();
+
+        assert!(hashed_name.starts_with("icon."));
+        assert!(output_dir.join(hashed_name).exists());
+    }
+
+    #[test]
+    fn test_convert_existing_files_hashed_is_idempotent() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_dir = temp_dir.path().join("input");
+        let output_dir = temp_dir.path().join("output");
+
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::write(input_dir.join("icon.svg"), create_test_svg()).unwrap();
+
+        convert_existing_files_hashed(
+            &input_dir,
+            &output_dir,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        )
+        .unwrap();
+        let manifest_data_first = fs::read_to_string(output_dir.join("manifest.json")).unwrap();
+
+        // Re-running without changing the source should leave the manifest untouched
+        convert_existing_files_hashed(
+            &input_dir,
+            &output_dir,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        )
+        .unwrap();
+        let manifest_data_second = fs::read_to_string(output_dir.join("manifest.json")).unwrap();
+
+        assert_eq!(manifest_data_first, manifest_data_second);
+    }
+
     #[test]
     fn test_convert_existing_files_empty_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -307,7 +1028,12 @@ mod tests {
         fs::create_dir_all(&output_dir).unwrap();
 
         // Convert with empty directory
-        let result = convert_existing_files(&input_dir, &output_dir);
+        let result = convert_existing_files(
+            &input_dir,
+            &output_dir,
+            &Arc::new(test_usvg_options()),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Empty directory conversion should succeed");
     }
@@ -327,7 +1053,12 @@ mod tests {
         fs::write(input_dir.join("data.json"), "{}").unwrap();
 
         // Convert - should only process .svg files
-        let result = convert_existing_files(&input_dir, &output_dir);
+        let result = convert_existing_files(
+            &input_dir,
+            &output_dir,
+            &Arc::new(test_usvg_options()),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Mixed directory conversion should succeed");
 
@@ -346,6 +1077,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_convert_leaves_no_temp_file_behind() {
+        let temp_dir = TempDir::new().unwrap();
+        let svg_path = temp_dir.path().join("test.svg");
+        let png_path = temp_dir.path().join("test.png");
+
+        fs::write(&svg_path, create_test_svg()).unwrap();
+
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
+
+        assert!(result.is_ok(), "Conversion should succeed");
+        assert!(png_path.exists(), "PNG file should be created");
+
+        // No leftover `.tmp-*` fragments should remain in the output directory
+        let leftover_tmp = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .any(|e| e.file_name().to_string_lossy().contains(".tmp-"));
+        assert!(!leftover_tmp, "No temp files should be left behind");
+    }
+
     #[test]
     fn test_convert_with_subdirectory_output() {
         let temp_dir = TempDir::new().unwrap();
@@ -356,9 +1113,147 @@ mod tests {
 
         fs::write(&svg_path, create_test_svg()).unwrap();
 
-        let result = convert(&svg_path, &png_path);
+        let result = convert(
+            &svg_path,
+            &png_path,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
 
         assert!(result.is_ok(), "Conversion to subdirectory should succeed");
         assert!(png_path.exists(), "PNG in subdirectory should exist");
     }
+
+    #[test]
+    fn test_target_dimensions_scale() {
+        assert_eq!(
+            target_dimensions(100.0, 50.0, Some(2.0), None, None),
+            (200, 100)
+        );
+    }
+
+    #[test]
+    fn test_target_dimensions_width_preserves_aspect() {
+        assert_eq!(
+            target_dimensions(100.0, 50.0, None, Some(40), None),
+            (40, 20)
+        );
+    }
+
+    #[test]
+    fn test_convert_to_jpeg_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let svg_path = temp_dir.path().join("test.svg");
+        let jpeg_path = temp_dir.path().join("test.jpg");
+
+        fs::write(&svg_path, create_test_svg()).unwrap();
+
+        let opts = RenderOptions {
+            format: OutputFormat::Jpeg,
+            ..RenderOptions::default()
+        };
+        let result = convert(&svg_path, &jpeg_path, &test_usvg_options(), &opts);
+
+        assert!(result.is_ok(), "JPEG conversion should succeed");
+        assert!(jpeg_path.exists(), "JPEG file should be created");
+        assert!(
+            image::open(&jpeg_path).is_ok(),
+            "Output should be a valid image"
+        );
+    }
+
+    #[test]
+    fn test_convert_with_explicit_scale() {
+        let temp_dir = TempDir::new().unwrap();
+        let svg_path = temp_dir.path().join("test.svg");
+        let png_path = temp_dir.path().join("test.png");
+
+        fs::write(&svg_path, create_test_svg()).unwrap();
+
+        let opts = RenderOptions {
+            scale: Some(2.0),
+            ..RenderOptions::default()
+        };
+        convert(&svg_path, &png_path, &test_usvg_options(), &opts).unwrap();
+
+        let image = image::open(&png_path).unwrap();
+        assert_eq!(image.width(), 200);
+        assert_eq!(image.height(), 200);
+    }
+
+    #[test]
+    fn test_flush_due_conversions_respects_debounce_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_dir = temp_dir.path().join("input");
+        let output_dir = temp_dir.path().join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let svg_path = input_dir.join("test.svg");
+        fs::write(&svg_path, create_test_svg()).unwrap();
+
+        let mut pending = HashMap::new();
+        pending.insert(svg_path.clone(), Instant::now());
+        let mut hashed_manifest = None;
+
+        // Not yet quiet long enough: nothing should be converted or removed from the queue.
+        flush_due_conversions(
+            &mut pending,
+            Duration::from_secs(60),
+            &input_dir,
+            &output_dir,
+            &mut hashed_manifest,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
+        assert!(pending.contains_key(&svg_path));
+        assert!(!output_dir.join("test.png").exists());
+
+        // A debounce window of zero means the path is immediately due.
+        flush_due_conversions(
+            &mut pending,
+            Duration::from_secs(0),
+            &input_dir,
+            &output_dir,
+            &mut hashed_manifest,
+            &test_usvg_options(),
+            &RenderOptions::default(),
+        );
+        assert!(pending.is_empty());
+        assert!(output_dir.join("test.png").exists());
+    }
+
+    #[test]
+    fn test_handle_watch_event_removes_output_on_delete() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_dir = temp_dir.path().join("input");
+        let output_dir = temp_dir.path().join("output");
+        fs::create_dir_all(&input_dir).unwrap();
+        fs::create_dir_all(&output_dir).unwrap();
+
+        let svg_path = input_dir.join("test.svg");
+        let png_path = output_dir.join("test.png");
+        fs::write(&png_path, b"stale png bytes").unwrap();
+
+        let mut pending = HashMap::new();
+        pending.insert(svg_path.clone(), Instant::now());
+        let mut hashed_manifest = None;
+
+        let remove_event = Event::new(EventKind::Remove(notify::event::RemoveKind::File))
+            .add_path(svg_path.clone());
+        handle_watch_event(
+            remove_event,
+            &mut pending,
+            &input_dir,
+            &output_dir,
+            &mut hashed_manifest,
+            &RenderOptions::default(),
+        );
+
+        assert!(
+            !pending.contains_key(&svg_path),
+            "removed path should not stay queued for conversion"
+        );
+        assert!(!png_path.exists(), "stale output should be deleted");
+    }
 }